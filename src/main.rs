@@ -3,12 +3,17 @@
 mod model;
 
 use std::path::PathBuf;
+use std::time::Duration;
 
 use eframe::egui;
-use egui::RichText;
+use egui::text::LayoutJob;
 use model::{BooleanModel, Document, DocumentDetails};
 use serde::{Deserialize, Serialize};
 
+/// How long the query must sit unchanged before we run the full
+/// boolean/positional evaluation, instead of the cheap prefix match.
+const QUERY_DEBOUNCE: Duration = Duration::from_millis(300);
+
 fn main() -> Result<(), eframe::Error> {
     let options = eframe::NativeOptions {
         initial_window_size: Some(egui::vec2(1280.0, 720.0)),
@@ -30,6 +35,14 @@ struct MyApp {
     can_close: bool,
     picked_path: Option<String>,
     model: BooleanModel,
+
+    /// Time (`ctx.input().time`) the query was last edited, for debouncing.
+    #[serde(skip)]
+    query_changed_at: f64,
+    /// The query string the full boolean/positional evaluation last ran
+    /// for, so we don't redo it every frame once the query has stabilized.
+    #[serde(skip)]
+    evaluated_query: Option<String>,
 }
 
 impl MyApp {
@@ -81,20 +94,43 @@ impl eframe::App for MyApp {
             }
 
             // Query Input
-            ui.text_edit_singleline(&mut self.query);
+            let query_edit = ui.text_edit_singleline(&mut self.query);
+
+            if query_edit.changed() {
+                self.query_changed_at = ctx.input(|i| i.time);
 
-            // Search button
-            if ui.button("Search").clicked() {
-                self.result = self.model.query_boolean(&self.query);
+                // Live filtering: while the last token is still being typed,
+                // short-circuit to a cheap prefix match instead of waiting
+                // for the debounce to run the full query.
+                self.result = match self.query.split_whitespace().last() {
+                    Some(last_token) => self.model.query_prefix(last_token),
+                    None => vec![],
+                };
             }
 
-            // Enter key handler
-            if ctx.input(|i| i.key_pressed(egui::Key::Enter)) {
-                if self.query.contains("/") {
-                    self.result = self.model.query_positional(&self.query);
+            let run_full_query = |model: &BooleanModel, query: &str| {
+                if query.contains('/') {
+                    model.query_positional(query)
                 } else {
-                    self.result = self.model.query_boolean(&self.query);
+                    model.query_boolean(query)
                 }
+            };
+
+            // Search button and Enter both force an immediate full evaluation
+            if ui.button("Search").clicked() || ctx.input(|i| i.key_pressed(egui::Key::Enter)) {
+                self.result = run_full_query(&self.model, &self.query);
+                self.evaluated_query = Some(self.query.clone());
+            }
+
+            // Once the query has sat unchanged for QUERY_DEBOUNCE, replace
+            // the prefix-matched preview with the real evaluation.
+            let stable = Duration::from_secs_f64(ctx.input(|i| i.time) - self.query_changed_at)
+                >= QUERY_DEBOUNCE;
+            if stable && self.evaluated_query.as_deref() != Some(self.query.as_str()) {
+                self.result = run_full_query(&self.model, &self.query);
+                self.evaluated_query = Some(self.query.clone());
+            } else if !stable {
+                ctx.request_repaint_after(QUERY_DEBOUNCE);
             }
 
             // Render results with summary on hover
@@ -126,7 +162,11 @@ impl eframe::App for MyApp {
             } else {
                 egui::ScrollArea::vertical().show(ui, |ui| {
                     ui.heading(&self.selected_document.name);
-                    ui.label(RichText::new(&self.selected_document.text))
+                    ui.label(highlight_query_terms(
+                        &self.selected_document.text,
+                        &self.query,
+                        ui,
+                    ))
                 });
             }
         });
@@ -138,8 +178,111 @@ impl eframe::App for MyApp {
                     name: String::from("Model JSON"),
                     summary: String::from("Model JSON"),
                     text: serde_json::to_string_pretty(&self.model).unwrap_or(String::from("")),
+                    ..Default::default()
                 }
             }
         });
     }
 }
+
+/// Extract the plain search terms out of a query, dropping boolean
+/// keywords/parens, the `/k` proximity marker, and `~`-fuzzy suffixes, for
+/// highlighting against the raw document text.
+fn query_terms(query: &str) -> Vec<String> {
+    query
+        .split_whitespace()
+        .map(|token| {
+            token
+                .trim_start_matches(|c: char| c == '-' || c == '(')
+                .trim_end_matches(')')
+        })
+        .filter(|token| !matches!(*token, "AND" | "OR" | "NOT" | "(" | ")"))
+        .filter(|token| !token.starts_with('/'))
+        .map(|token| token.split('~').next().unwrap_or(token).to_lowercase())
+        .filter(|token| !token.is_empty())
+        .collect()
+}
+
+/// Lay out `text` with every (case-insensitive, un-stemmed) occurrence of a
+/// `query` term highlighted, for the document preview panel.
+fn highlight_query_terms(text: &str, query: &str, ui: &egui::Ui) -> LayoutJob {
+    let terms = query_terms(query);
+
+    let body_format = egui::TextFormat {
+        color: ui.visuals().text_color(),
+        ..Default::default()
+    };
+    let highlight_format = egui::TextFormat {
+        color: ui.visuals().strong_text_color(),
+        background: ui.visuals().selection.bg_fill,
+        ..Default::default()
+    };
+
+    let mut job = LayoutJob::default();
+    if terms.is_empty() {
+        job.append(text, 0.0, body_format);
+        return job;
+    }
+
+    let mut cursor = 0;
+
+    while cursor < text.len() {
+        let next_match = text[cursor..].char_indices().find_map(|(offset, _)| {
+            terms
+                .iter()
+                .find_map(|term| match_len_at(text, cursor + offset, term))
+                .map(|len| (offset, len))
+        });
+
+        match next_match {
+            Some((offset, len)) => {
+                if offset > 0 {
+                    job.append(&text[cursor..cursor + offset], 0.0, body_format.clone());
+                }
+                job.append(
+                    &text[cursor + offset..cursor + offset + len],
+                    0.0,
+                    highlight_format.clone(),
+                );
+                cursor += offset + len;
+            }
+            None => {
+                job.append(&text[cursor..], 0.0, body_format.clone());
+                break;
+            }
+        }
+    }
+
+    job
+}
+
+/// If `term` (already lowercase) matches `text` starting at byte offset
+/// `start`, case-insensitively, return the byte length of the match *in
+/// `text`*. Compares per-char lowercase expansions rather than lowercasing
+/// `text` up front, so it never assumes lowercasing preserves UTF-8 byte
+/// length (e.g. `İ` lowercases to two chars) and can't produce an
+/// out-of-bounds/mid-codepoint slice.
+fn match_len_at(text: &str, start: usize, term: &str) -> Option<usize> {
+    let mut term_chars = term.chars();
+    let mut pending = term_chars.next();
+    let mut consumed = 0;
+
+    for (offset, ch) in text[start..].char_indices() {
+        if pending.is_none() {
+            break;
+        }
+        for lower_ch in ch.to_lowercase() {
+            match pending {
+                Some(expected) if expected == lower_ch => pending = term_chars.next(),
+                _ => return None,
+            }
+        }
+        consumed = offset + ch.len_utf8();
+    }
+
+    if pending.is_none() {
+        Some(consumed)
+    } else {
+        None
+    }
+}
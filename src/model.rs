@@ -5,6 +5,7 @@ use std::fs;
 use std::hash::Hash;
 use std::path::{Path, PathBuf};
 
+use roaring::RoaringBitmap;
 use rust_stemmers::{Algorithm, Stemmer};
 use serde::{Deserialize, Serialize};
 
@@ -47,23 +48,140 @@ pub struct DocumentDetails {
     pub name: String,
     pub summary: String,
     pub text: String,
+    /// Number of indexed (post-stopword) tokens, used as `dl` in BM25 scoring.
+    pub length: u32,
 }
 
 fn stemmer_default() -> Stemmer {
     Stemmer::create(Algorithm::English)
 }
 
-/// Boolean Query Operators
-enum Op {
-    AND,
-    OR,
-    NONE,
+/// AST for a parsed boolean query, built by `QueryParser` and evaluated by
+/// `BooleanModel::eval_node`. AND binds tighter than OR, and `NOT`/`-` bind
+/// tighter than both.
+#[derive(Debug, Clone)]
+enum QueryNode {
+    Term(String),
+    /// A term suffixed with `~` (e.g. `colour~2`), matched against the
+    /// dictionary within `Option<u8>` edits or, if `None`, a default scaled
+    /// to the term's length (see `BooleanModel::default_max_edits`).
+    Fuzzy(String, Option<u8>),
+    And(Box<QueryNode>, Box<QueryNode>),
+    Or(Box<QueryNode>, Box<QueryNode>),
+    Not(Box<QueryNode>),
+}
+
+/// Build a `Term`/`Fuzzy` leaf node from a single query token, splitting off
+/// a trailing `~` or `~N` fuzzy-match suffix if present.
+fn term_node(token: &str) -> QueryNode {
+    match token.find('~') {
+        Some(idx) => {
+            let (base, suffix) = (&token[..idx], &token[idx + 1..]);
+            QueryNode::Fuzzy(base.to_lowercase(), suffix.parse::<u8>().ok())
+        }
+        None => QueryNode::Term(token.to_lowercase()),
+    }
+}
+
+/// Recursive-descent parser over a tokenized boolean query.
+///
+/// Grammar (OR lowest precedence, NOT highest):
+/// ```text
+/// expr   := and ('OR' and)*
+/// and    := unary ('AND' unary)*
+/// unary  := ('NOT' | '-') unary | primary
+/// primary := '(' expr ')' | TERM
+/// ```
+struct QueryParser<'a> {
+    tokens: &'a [String],
+    pos: usize,
+}
+
+impl<'a> QueryParser<'a> {
+    fn new(tokens: &'a [String]) -> Self {
+        Self { tokens, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.pos).map(|s| s.as_str())
+    }
+
+    fn advance(&mut self) -> Option<&'a str> {
+        let tok = self.peek();
+        self.pos += 1;
+        tok
+    }
+
+    fn parse(&mut self) -> QueryNode {
+        self.parse_or()
+    }
+
+    fn parse_or(&mut self) -> QueryNode {
+        let mut node = self.parse_and();
+        while self.peek() == Some("OR") {
+            self.advance();
+            let rhs = self.parse_and();
+            node = QueryNode::Or(Box::new(node), Box::new(rhs));
+        }
+        node
+    }
+
+    fn parse_and(&mut self) -> QueryNode {
+        let mut node = self.parse_unary();
+        while self.peek() == Some("AND") {
+            self.advance();
+            let rhs = self.parse_unary();
+            node = QueryNode::And(Box::new(node), Box::new(rhs));
+        }
+        node
+    }
+
+    fn parse_unary(&mut self) -> QueryNode {
+        match self.peek() {
+            Some("NOT") | Some("-") => {
+                self.advance();
+                QueryNode::Not(Box::new(self.parse_unary()))
+            }
+            Some(tok) if tok.starts_with('-') && tok.len() > 1 => {
+                let term = tok[1..].to_string();
+                self.advance();
+                QueryNode::Not(Box::new(term_node(&term)))
+            }
+            _ => self.parse_primary(),
+        }
+    }
+
+    fn parse_primary(&mut self) -> QueryNode {
+        match self.advance() {
+            Some("(") => {
+                let node = self.parse_or();
+                if self.peek() == Some(")") {
+                    self.advance();
+                }
+                node
+            }
+            Some(term) => term_node(term),
+            None => QueryNode::Term(String::new()),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct BooleanModel {
-    posting_list: HashMap<String, Vec<Document>>,
+    /// Term -> set of document ids containing it, as a compressed bitmap.
+    /// `RoaringBitmap` only implements `Serialize`/`Deserialize` when the
+    /// `roaring` crate is pulled in with `features = ["serde"]` in
+    /// Cargo.toml — required for `BooleanModel` (and "View model") to
+    /// compile at all.
+    posting_list: HashMap<String, RoaringBitmap>,
+    /// Term -> doc id -> sorted token positions, only consulted for
+    /// positional queries and BM25 term frequency, never for plain AND/OR/NOT.
+    positions: HashMap<String, HashMap<u32, Vec<u32>>>,
     documents: HashMap<u32, DocumentDetails>,
+    /// Query-time synonym expansion, keyed by the original (unstemmed,
+    /// lowercase) term. Values may be multi-word phrases, e.g.
+    /// `"nyc" -> ["new york"]`. Set via `set_synonyms`.
+    synonyms: HashMap<String, Vec<String>>,
 
     #[serde(skip, default = "stemmer_default")]
     stemmer: Stemmer,
@@ -79,18 +197,32 @@ impl BooleanModel {
     pub fn new() -> Self {
         Self {
             posting_list: HashMap::new(),
+            positions: HashMap::new(),
             documents: HashMap::new(),
+            synonyms: HashMap::new(),
             stemmer: stemmer_default(),
         }
     }
 
+    /// Configure query-time synonym expansion. `synonyms` maps a lowercase
+    /// term to the (possibly multi-word) terms it should also match, e.g.
+    /// `{"nyc": ["new york"]}` makes a query for `nyc` also match documents
+    /// containing every word of "new york" (an AND of their bitmaps, not a
+    /// proximity/adjacency requirement). Applied uniformly by both
+    /// `query_boolean` and `query_ranked`; `query_positional` is
+    /// proximity-based and is not expanded.
+    pub fn set_synonyms(&mut self, synonyms: HashMap<String, Vec<String>>) {
+        self.synonyms = synonyms;
+    }
+
     /// This public function takes in a directory containing text files only
     /// Creates a posting list by:
     /// 1. Filtering text (removing non alphanumeric symbols)
     /// 2. Tokenizing by splitting on space
     /// 3. Removing stopwords from tokens
     /// 4. Stemming tokens
-    /// 5. Adding Documents to the posting list with positions
+    /// 5. Adding the document id to each stemmed term's bitmap, and its
+    ///    positions to the positions side-table
     pub fn index(self: &mut Self, data_dir: PathBuf) {
         let files = BooleanModel::list_dir_sorted(&data_dir);
 
@@ -108,19 +240,29 @@ impl BooleanModel {
                 .to_lowercase()
                 .replace(|c: char| !c.is_ascii_alphanumeric(), " ");
 
-            BooleanModel::tokenize(&filtered_text)
+            let tokens: Vec<&str> = BooleanModel::tokenize(&filtered_text)
                 .filter(|t| !STOPWORDS.contains(t))
-                .into_iter()
-                .enumerate()
-                .for_each(|(j, token)| {
-                    self.insert(
-                        &self.stem(token),
-                        Document {
-                            id: doc_id,
-                            positions: LinkedList::from([j as u32]),
-                        },
-                    )
-                });
+                .collect();
+            let length = tokens.len() as u32;
+
+            let mut doc_positions: HashMap<String, Vec<u32>> = HashMap::new();
+            for (j, token) in tokens.into_iter().enumerate() {
+                doc_positions
+                    .entry(self.stem(token))
+                    .or_default()
+                    .push(j as u32);
+            }
+
+            for (term, term_positions) in doc_positions {
+                self.posting_list
+                    .entry(term.clone())
+                    .or_default()
+                    .insert(doc_id);
+                self.positions
+                    .entry(term)
+                    .or_default()
+                    .insert(doc_id, term_positions);
+            }
 
             self.documents.insert(
                 doc_id,
@@ -128,56 +270,70 @@ impl BooleanModel {
                     name: String::from(file.file_name().unwrap().to_str().unwrap()),
                     summary: String::from(filtered_text.get(0..50).unwrap_or_default()),
                     text,
+                    length,
                 },
             );
         }
     }
 
-    /// Takes in a `query` of the form "X AND Y OR Z ..."
-    /// Returns vector of `Document`s by applying intersection or union to document lists
+    /// Takes in a `query` of the form "X AND (Y OR Z) AND NOT W ..."
+    /// Parses the query into a `QueryNode` tree (AND binds tighter than OR,
+    /// `NOT`/`-` bind tighter than both, `(...)` groups) and evaluates it
+    /// recursively as bitmap `&`/`|`/andnot operations, treating `NOT` as a
+    /// complement against the bitmap of all document ids. A term suffixed
+    /// with `~` or `~N` (e.g. `colour~2`) is matched fuzzily against the
+    /// dictionary instead of exactly, see `fuzzy_terms`.
     pub fn query_boolean(self: &Self, query: &str) -> Vec<Document> {
-        let mut op = Op::NONE;
+        let tokens = BooleanModel::tokenize_query(query);
+        let ast = QueryParser::new(&tokens).parse();
+        let universe = self.universe_bitmap();
 
-        let docs =
-            BooleanModel::tokenize(&String::from(query)).fold(vec![], |ans, token| match token {
-                "AND" => {
-                    op = Op::AND;
-                    ans
-                }
-                "OR" => {
-                    op = Op::OR;
-                    ans
-                }
-                other => {
-                    let docs = self
-                        .get_docs(other.to_lowercase().as_str())
-                        .unwrap_or(vec![]);
-
-                    match op {
-                        Op::AND => {
-                            return BooleanModel::intersect(&ans, &docs);
-                        }
-                        Op::OR => {
-                            return BooleanModel::union(&ans, &docs);
-                        }
-                        Op::NONE => {
-                            if ans.is_empty() {
-                                return docs;
-                            }
-                        }
-                    }
-                    op = Op::NONE;
-                    ans
-                }
-            });
+        self.eval_node(&ast, &universe)
+            .into_iter()
+            .map(|id| Document {
+                id,
+                positions: LinkedList::new(),
+            })
+            .collect()
+    }
 
-        docs.into_iter().map(|f| f.clone()).collect()
+    /// Split a query into terms plus standalone `(` / `)` tokens so grouping
+    /// survives even when parentheses are typed flush against a term.
+    fn tokenize_query(query: &str) -> Vec<String> {
+        query
+            .replace('(', " ( ")
+            .replace(')', " ) ")
+            .split_whitespace()
+            .map(String::from)
+            .collect()
+    }
+
+    /// Bitmap of every document id currently indexed, i.e. the universe
+    /// `NOT` complements against.
+    fn universe_bitmap(self: &Self) -> RoaringBitmap {
+        self.documents.keys().cloned().collect()
+    }
+
+    /// Recursively evaluate a `QueryNode` into a bitmap of matching document
+    /// ids, using bitmap `&`/`|`/andnot for `And`/`Or`/`Not`.
+    fn eval_node(self: &Self, node: &QueryNode, universe: &RoaringBitmap) -> RoaringBitmap {
+        match node {
+            QueryNode::Term(term) => self.expand_term_bitmap(term),
+            QueryNode::Fuzzy(term, max_edits) => {
+                let max_edits = max_edits.unwrap_or_else(|| BooleanModel::default_max_edits(term));
+                self.fuzzy_bitmap(term, max_edits)
+            }
+            QueryNode::And(lhs, rhs) => &self.eval_node(lhs, universe) & &self.eval_node(rhs, universe),
+            QueryNode::Or(lhs, rhs) => &self.eval_node(lhs, universe) | &self.eval_node(rhs, universe),
+            QueryNode::Not(inner) => universe - &self.eval_node(inner, universe),
+        }
     }
 
     /// Takes in a `query` of the form "X Y Z ... /k"
-    /// Returns vector of `Document`s by intersecting based on term proximity in the document
+    /// Intersects the terms' bitmaps to find candidate documents, then
+    /// fetches positions only for those survivors to check term proximity.
     pub fn query_positional(self: &Self, query: &str) -> Vec<Document> {
-        let mut docs_list = vec![];
+        let mut terms = vec![];
         let mut k = 1;
 
         for token in BooleanModel::tokenize(&String::from(query)) {
@@ -186,151 +342,274 @@ impl BooleanModel {
                     Ok(tk) => k = tk,
                     Err(_) => k = 1,
                 },
-                term => {
-                    docs_list.push(
-                        self.get_docs(term.to_lowercase().as_str())
-                            .unwrap_or(vec![]),
-                    );
-                }
+                term => terms.push(self.stem(&term.to_lowercase())),
             }
         }
 
-        let docs = docs_list
+        if terms.is_empty() {
+            return vec![];
+        }
+
+        let candidates = terms[1..]
+            .iter()
+            .fold(self.term_bitmap(&terms[0]), |acc, term| &acc & &self.term_bitmap(term));
+
+        candidates
             .into_iter()
-            .enumerate()
-            .fold(vec![], |ans, (i, cur)| {
-                if i == 0 {
-                    return cur;
+            .filter(|doc_id| {
+                terms.windows(2).all(|pair| {
+                    match (
+                        self.positions_for(&pair[0], *doc_id),
+                        self.positions_for(&pair[1], *doc_id),
+                    ) {
+                        (Some(a), Some(b)) => BooleanModel::positions_within(a, b, k),
+                        _ => false,
+                    }
+                })
+            })
+            .map(|id| Document {
+                id,
+                positions: self
+                    .positions_for(terms.last().unwrap(), id)
+                    .map(|p| p.iter().cloned().collect())
+                    .unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    /// Takes in a `query` of space-separated terms and scores every matching
+    /// document with BM25 (k1=1.5, b=0.75), returning the `top_k` highest
+    /// scoring `Document`s alongside their score, descending.
+    pub fn query_ranked(self: &Self, query: &str, top_k: usize) -> Vec<(Document, f32)> {
+        const K1: f32 = 1.5;
+        const B: f32 = 0.75;
+
+        let n = self.documents.len() as f32;
+        if n == 0.0 {
+            return vec![];
+        }
+        let avgdl = self.documents.values().map(|d| d.length as f32).sum::<f32>() / n;
+
+        let mut scores: HashMap<u32, f32> = HashMap::new();
+
+        for token in BooleanModel::tokenize(&String::from(query)) {
+            if matches!(token, "AND" | "OR" | "NOT" | "-" | "(" | ")") {
+                continue;
+            }
+
+            let lowered = token.to_lowercase();
+            // Like `expand_term_bitmap`/`phrase_bitmap`, a multi-word synonym
+            // only contributes to documents containing every one of its
+            // words (an AND), not to each word independently.
+            let mut phrases = vec![lowered.clone()];
+            if let Some(synonyms) = self.synonyms.get(&lowered) {
+                phrases.extend(synonyms.iter().cloned());
+            }
+
+            for phrase in phrases {
+                let phrase_doc_ids = self.phrase_bitmap(&phrase);
+                if phrase_doc_ids.is_empty() {
+                    continue;
                 }
-                BooleanModel::positional_intersect(&ans, &cur, k)
-            });
 
-        docs.into_iter().map(|f| f.clone()).collect()
+                for word in phrase.split_whitespace() {
+                    let term = self.stem(word);
+                    let bitmap = self.posting_list.get(&term).cloned().unwrap_or_default();
+                    let n_t = bitmap.len() as f32;
+                    if n_t == 0.0 {
+                        continue;
+                    }
+                    let idf = ((n - n_t + 0.5) / (n_t + 0.5) + 1.0).ln();
+
+                    for doc_id in phrase_doc_ids.iter() {
+                        let tf = self
+                            .positions_for(&term, doc_id)
+                            .map(|p| p.len())
+                            .unwrap_or(0) as f32;
+                        let dl = self
+                            .documents
+                            .get(&doc_id)
+                            .map(|details| details.length as f32)
+                            .unwrap_or(avgdl);
+
+                        let score =
+                            idf * (tf * (K1 + 1.0)) / (tf + K1 * (1.0 - B + B * (dl / avgdl)));
+                        *scores.entry(doc_id).or_insert(0.0) += score;
+                    }
+                }
+            }
+        }
+
+        let mut ranked: Vec<(Document, f32)> = scores
+            .into_iter()
+            .map(|(id, score)| {
+                (
+                    Document {
+                        id,
+                        positions: LinkedList::new(),
+                    },
+                    score,
+                )
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+        ranked.truncate(top_k);
+
+        ranked
+    }
+
+    /// Union of the bitmaps of every dictionary term whose stem starts with
+    /// the stemmed `prefix`. Cheap enough to run on every keystroke, used by
+    /// the front-end for live filtering while the final query token is
+    /// still being typed.
+    pub fn query_prefix(self: &Self, prefix: &str) -> Vec<Document> {
+        let stemmed_prefix = self.stem(prefix);
+
+        self.posting_list
+            .iter()
+            .filter(|(term, _)| term.starts_with(&stemmed_prefix))
+            .fold(RoaringBitmap::new(), |acc, (_, bitmap)| &acc | bitmap)
+            .into_iter()
+            .map(|id| Document {
+                id,
+                positions: LinkedList::new(),
+            })
+            .collect()
     }
 
     pub fn get_doc(self: &Self, id: u32) -> Option<&DocumentDetails> {
         return self.documents.get(&id);
     }
 
-    /// Return new vector containing references of `Document`s containing a `term`
-    fn get_docs(self: &Self, term: &str) -> Option<Vec<&Document>> {
+    /// Document-id bitmap for a (stemmed) `term`, empty if never indexed.
+    fn term_bitmap(self: &Self, term: &str) -> RoaringBitmap {
         self.posting_list
             .get(&self.stem(term))
-            .and_then(|list| Some(list.iter().collect()))
-    }
-
-    fn union<'a>(a: &Vec<&'a Document>, b: &Vec<&'a Document>) -> Vec<&'a Document> {
-        let mut result = vec![];
-
-        let mut i = 0;
-        let mut j = 0;
-
-        while i < a.len() && j < b.len() {
-            if a[i].id == b[j].id {
-                result.push(b[j]);
-                i = i + 1;
-                j = j + 1;
-            } else if a[i].id < b[j].id {
-                result.push(a[i]);
-                i = i + 1;
-            } else {
-                result.push(b[j]);
-                j = j + 1;
-            }
-        }
+            .cloned()
+            .unwrap_or_default()
+    }
 
-        while i < a.len() {
-            result.push(a[i]);
-            i = i + 1;
-        }
+    /// `term_bitmap` unioned with the bitmap of every configured synonym of
+    /// `term` (looked up by `term`'s original, unstemmed spelling).
+    fn expand_term_bitmap(self: &Self, term: &str) -> RoaringBitmap {
+        let mut result = self.term_bitmap(term);
 
-        while j < b.len() {
-            result.push(a[j]);
-            j = j + 1;
+        if let Some(synonyms) = self.synonyms.get(term) {
+            for synonym in synonyms {
+                result = &result | &self.phrase_bitmap(synonym);
+            }
         }
 
         result
     }
 
-    fn intersect<'a>(a: &Vec<&'a Document>, b: &Vec<&'a Document>) -> Vec<&'a Document> {
-        let mut result = vec![];
+    /// Bitmap of documents containing every word of a (possibly multi-word)
+    /// synonym phrase, i.e. the AND of each word's bitmap.
+    fn phrase_bitmap(self: &Self, phrase: &str) -> RoaringBitmap {
+        phrase
+            .split_whitespace()
+            .map(|word| self.term_bitmap(word))
+            .reduce(|acc, bitmap| &acc & &bitmap)
+            .unwrap_or_default()
+    }
 
-        let mut i = 0;
-        let mut j = 0;
+    /// Indexed positions of an already-stemmed `term` within `doc_id`.
+    fn positions_for(self: &Self, stemmed_term: &str, doc_id: u32) -> Option<&Vec<u32>> {
+        self.positions.get(stemmed_term)?.get(&doc_id)
+    }
 
-        while i < a.len() && j < b.len() {
-            if a[i].id == b[j].id {
-                result.push(b[j]);
-                i = i + 1;
-                j = j + 1;
-            } else if a[i].id < b[j].id {
-                i = i + 1;
-            } else {
-                j = j + 1;
+    fn positions_within(a: &[u32], b: &[u32], k: u32) -> bool {
+        for pp1 in a {
+            for pp2 in b {
+                if pp1.abs_diff(*pp2) <= k {
+                    return true;
+                } else if pp2 > pp1 {
+                    break;
+                }
             }
         }
+        false
+    }
 
-        result
+    /// Default edit-distance budget for fuzzy matching, scaled to term
+    /// length: 0 for very short terms, 1 for medium, 2 otherwise.
+    fn default_max_edits(term: &str) -> u8 {
+        match term.len() {
+            0..=3 => 0,
+            4..=6 => 1,
+            _ => 2,
+        }
     }
 
-    fn positional_intersect<'a>(
-        a: &Vec<&'a Document>,
-        b: &Vec<&'a Document>,
-        k: u32,
-    ) -> Vec<&'a Document> {
-        let mut answer = vec![];
+    /// Dictionary terms (posting list keys) within `max_edits` Damerau-Levenshtein
+    /// edits of `term`, after stemming `term` the same way indexed terms are stemmed.
+    fn fuzzy_terms(self: &Self, term: &str, max_edits: u8) -> Vec<&String> {
+        let stemmed = self.stem(term);
+        self.posting_list
+            .keys()
+            .filter(|key| BooleanModel::bounded_edit_distance(&stemmed, key, max_edits).is_some())
+            .collect()
+    }
 
-        let mut i = 0;
-        let mut j = 0;
+    /// Union of the bitmaps of every dictionary term within `max_edits` of
+    /// `term`, used to make a query term typo-tolerant.
+    fn fuzzy_bitmap(self: &Self, term: &str, max_edits: u8) -> RoaringBitmap {
+        self.fuzzy_terms(term, max_edits)
+            .into_iter()
+            .fold(RoaringBitmap::new(), |acc, matched| match self.posting_list.get(matched) {
+                Some(bitmap) => &acc | bitmap,
+                None => acc,
+            })
+    }
 
-        while i < a.len() && j < b.len() {
-            if a[i].id == b[j].id {
-                let mut ok = false;
+    /// Bounded Damerau-Levenshtein distance (insertions, deletions,
+    /// substitutions, and adjacent transpositions) between `a` and `b`.
+    /// Returns `None` as soon as every entry in a DP row exceeds `max_edits`,
+    /// so the dictionary can be scanned without paying for the full matrix
+    /// on obviously-distant terms.
+    fn bounded_edit_distance(a: &str, b: &str, max_edits: u8) -> Option<u8> {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        let (n, m) = (a.len(), b.len());
+
+        if (n as i64 - m as i64).unsigned_abs() as u8 > max_edits {
+            return None;
+        }
 
-                for pp1 in &a[i].positions {
-                    if ok {
-                        answer.push(b[j]);
-                        break;
-                    }
+        let max_edits = max_edits as usize;
+        let mut prev2: Vec<usize> = vec![0; m + 1];
+        let mut prev: Vec<usize> = (0..=m).collect();
+        let mut curr: Vec<usize> = vec![0; m + 1];
 
-                    for pp2 in &b[j].positions {
-                        if pp1.abs_diff(*pp2) <= k {
-                            ok = true;
-                        } else if pp2 > pp1 {
-                            break;
-                        }
-                    }
+        for i in 1..=n {
+            curr[0] = i;
+            let mut row_min = curr[0];
+
+            for j in 1..=m {
+                let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+                let mut val = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+
+                if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                    val = val.min(prev2[j - 2] + 1);
                 }
-                i = i + 1;
-                j = j + 1;
-            } else if a[i].id < b[j].id {
-                i = i + 1;
-            } else {
-                j = j + 1;
+
+                curr[j] = val;
+                row_min = row_min.min(val);
             }
-        }
 
-        answer
-    }
-
-    /// Adds document to posting list based on these criterias:
-    /// 1. if posting list contains `term` and the `document`: append to `positions`
-    /// 2. if posting list contains `term`: insert document`
-    /// 3. else insert new vector with the document to `posting_list[term]`
-    fn insert(&mut self, term: &str, mut document: Document) {
-        if self.posting_list.contains_key(term) {
-            let idx_result =
-                self.posting_list[term].binary_search_by(|doc| doc.id.cmp(&document.id));
-            match idx_result {
-                Ok(idx) => {
-                    self.posting_list.get_mut(term).unwrap()[idx]
-                        .positions
-                        .append(&mut document.positions);
-                }
-                Err(_) => self.posting_list.get_mut(term).unwrap().push(document),
+            if row_min > max_edits {
+                return None;
             }
+
+            std::mem::swap(&mut prev2, &mut prev);
+            std::mem::swap(&mut prev, &mut curr);
+        }
+
+        if prev[m] <= max_edits {
+            Some(prev[m] as u8)
         } else {
-            self.posting_list.insert(term.to_string(), vec![document]);
+            None
         }
     }
 